@@ -1,17 +1,58 @@
-use super::tokens::{BinaryOperator, InfixToken, PostfixToken, StackToken};
+use super::tokens::{
+    Associativity, BinaryOperator, InfixToken, PostfixToken, StackToken, UnaryOperator,
+};
 use std::{error::Error, fmt};
 
 /// An error during infix to postfix conversion.
 #[derive(Debug, PartialEq, Eq)]
 pub enum ConvertError {
     /// The number of [`GroupStart`](InfixToken::GroupStart) and [`GroupEnd`](InfixToken::GroupEnd) tokens did not match.
+    ///
+    /// Holds the count of unmatched `GroupStart`s minus the count of unmatched `GroupEnd`s. This
+    /// can be `0` even though the stream is unbalanced, if it contains both a `GroupEnd` with no
+    /// open group to close and a `GroupStart` that is never closed; treat this variant itself,
+    /// not its payload, as the authoritative signal that conversion failed.
     UnbalancedGroups(i32),
+    /// Two [`BinaryOp`](InfixToken::BinaryOp)s of equal precedence and [`Associativity::None`]
+    /// fixity ended up adjacent, e.g. chained comparisons like `a < b < c`.
+    NonAssociativeChain,
+    /// An [`ArgSeparator`](InfixToken::ArgSeparator) appeared outside of a function's argument
+    /// list, e.g. a stray comma at the top level or inside a plain (non-function) group.
+    StrayArgSeparator,
+    /// A [`Function`](InfixToken::Function) token was not immediately followed by a
+    /// [`GroupStart`](InfixToken::GroupStart) opening its argument list.
+    UnmatchedFunction,
+    /// An operator in the token stream, at the given index of the resulting [`PostfixToken`]s,
+    /// did not have enough operands available on the evaluation stack. Only returned by
+    /// [`convert_checked`].
+    MissingOperand(usize),
+    /// The token stream did not reduce to a single value: the evaluation stack held the given
+    /// number of values once every token had been processed. Only returned by [`convert_checked`].
+    IncompleteExpression(usize),
 }
 
 impl fmt::Display for ConvertError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ConvertError::UnbalancedGroups(_) => write!(f, "Unbalanced groups"),
+            ConvertError::NonAssociativeChain => {
+                write!(f, "Non-associative operators chained together")
+            }
+            ConvertError::StrayArgSeparator => {
+                write!(f, "Argument separator outside of a function call")
+            }
+            ConvertError::UnmatchedFunction => {
+                write!(
+                    f,
+                    "Function token not immediately followed by a group start"
+                )
+            }
+            ConvertError::MissingOperand(index) => {
+                write!(f, "Operator at postfix index {index} is missing an operand")
+            }
+            ConvertError::IncompleteExpression(depth) => {
+                write!(f, "Expression left {depth} values on the evaluation stack")
+            }
         }
     }
 }
@@ -33,67 +74,263 @@ impl Error for ConvertError {}
 /// # Errors
 ///
 /// See [`ConvertError`].
-pub fn convert<Operand, BinaryOp, I>(
+pub fn convert<Operand, BinaryOp, UnaryOp, Name, I>(
     tokens: I,
-) -> Result<Vec<PostfixToken<Operand, BinaryOp>>, ConvertError>
+) -> Result<Vec<PostfixToken<Operand, BinaryOp, UnaryOp, Name>>, ConvertError>
 where
-    I: IntoIterator<Item = InfixToken<Operand, BinaryOp>>,
+    I: IntoIterator<Item = InfixToken<Operand, BinaryOp, UnaryOp, Name>>,
     BinaryOp: BinaryOperator,
+    UnaryOp: UnaryOperator,
 {
     let mut result = vec![];
-    let mut stack: Vec<StackToken<BinaryOp>> = vec![];
-    let mut group_depth = 0;
-
-    tokens.into_iter().for_each(|token| match token {
-        InfixToken::Operand(name) => result.push(PostfixToken::Operand(name)),
-        InfixToken::BinaryOp(op) => {
-            while stack
-                .last()
-                .map_or(false, |last| stack_to_result(last, &op))
-            {
-                // Safe to `unwrap` because `stack.last()` returned `Some`
-                result.push(stack.pop().unwrap().into());
-            }
+    let mut stack: Vec<StackToken<BinaryOp, UnaryOp, Name>> = vec![];
+    // The number of `GroupStart`s currently open, i.e. not yet matched by a `GroupEnd`.
+    let mut open_groups: usize = 0;
+    // The number of `GroupEnd`s that had no open group to close. Tracked separately from
+    // `open_groups` rather than folded into a single signed counter, so that a stray `GroupEnd`
+    // can never be masked by an unrelated, later `GroupStart` netting back to zero.
+    let mut unmatched_group_ends: usize = 0;
 
-            stack.push(StackToken::BinaryOp(op));
-        }
-        InfixToken::GroupStart => {
-            stack.push(StackToken::GroupStart);
-            group_depth += 1;
+    // Whether the group currently open at each depth is a function call's argument list, so
+    // that `GroupEnd` and `ArgSeparator` know how to treat it. Kept in lock-step with `open_groups`.
+    let mut group_is_call: Vec<bool> = vec![];
+    // One entry per currently-open function call: the running argument count, and the `result`
+    // length at the moment the call's argument list was opened (to detect an empty `f()` call).
+    let mut call_arg_counts: Vec<usize> = vec![];
+    let mut call_start_lens: Vec<usize> = vec![];
+
+    for token in tokens {
+        // A `Function` token must be immediately followed by the `GroupStart` of its argument
+        // list; anything else leaves it stranded on the operator stack with no way to resolve
+        // into a `FunctionCall`.
+        if !matches!(token, InfixToken::GroupStart)
+            && matches!(stack.last(), Some(StackToken::Function(_)))
+        {
+            return Err(ConvertError::UnmatchedFunction);
         }
-        InfixToken::GroupEnd => {
-            while let Some(last) = stack.pop() {
-                match last {
-                    StackToken::BinaryOp(op) => result.push(PostfixToken::BinaryOp(op)),
-                    StackToken::GroupStart => break,
+
+        match token {
+            InfixToken::Operand(name) => result.push(PostfixToken::Operand(name)),
+            InfixToken::BinaryOp(op) => {
+                while let Some(last) = stack.last() {
+                    if !stack_to_result(last, op.precedence(), op.associativity()) {
+                        break;
+                    }
+
+                    // Check against the operator actually being popped (and so becoming
+                    // adjacent to `op` in `result`), not just the one on the stack before any
+                    // popping happens: a higher-precedence operator can sit between two
+                    // equal-precedence, non-associative ones right up until this point.
+                    if let StackToken::BinaryOp(last_op) = last {
+                        if last_op.precedence() == op.precedence()
+                            && (last_op.associativity() == Associativity::None
+                                || op.associativity() == Associativity::None)
+                        {
+                            return Err(ConvertError::NonAssociativeChain);
+                        }
+                    }
+
+                    // Safe to `unwrap` because `stack.last()` returned `Some`
+                    result.push(stack.pop().unwrap().into());
+                }
+
+                // A right-associative `op` stops short of popping an equal-precedence operator,
+                // so also check the operator it is about to be stacked on top of.
+                if let Some(StackToken::BinaryOp(last_op)) = stack.last() {
+                    if last_op.precedence() == op.precedence()
+                        && (last_op.associativity() == Associativity::None
+                            || op.associativity() == Associativity::None)
+                    {
+                        return Err(ConvertError::NonAssociativeChain);
+                    }
+                }
+
+                stack.push(StackToken::BinaryOp(op));
+            }
+            InfixToken::PrefixOp(op) => {
+                // A prefix operator binds to whatever operand follows it tighter than any
+                // operator already on the stack: each of those is still waiting for its right
+                // operand, which starts with this prefix operator. So nothing is popped here,
+                // not even another prefix operator pushed immediately before it; it is popped
+                // later by the usual precedence rules, like any stacked operator.
+                stack.push(StackToken::PrefixOp(op));
+            }
+            InfixToken::PostfixOp(op) => result.push(PostfixToken::UnaryOp(op)),
+            InfixToken::Function(name) => stack.push(StackToken::Function(name)),
+            InfixToken::ArgSeparator => {
+                if group_is_call.last() != Some(&true) {
+                    return Err(ConvertError::StrayArgSeparator);
+                }
+
+                while let Some(last) = stack.last() {
+                    if matches!(last, StackToken::GroupStart) {
+                        break;
+                    }
+                    // Safe to `unwrap` because `stack.last()` returned `Some`
+                    result.push(stack.pop().unwrap().into());
+                }
+
+                // Safe to `unwrap` because `group_is_call.last() == Some(&true)` guarantees a
+                // matching call frame exists
+                *call_arg_counts.last_mut().unwrap() += 1;
+            }
+            InfixToken::GroupStart => {
+                let is_call = matches!(stack.last(), Some(StackToken::Function(_)));
+                if is_call {
+                    call_arg_counts.push(1);
+                    call_start_lens.push(result.len());
+                }
+                group_is_call.push(is_call);
+
+                stack.push(StackToken::GroupStart);
+                open_groups += 1;
+            }
+            InfixToken::GroupEnd => {
+                // Whether the pop loop below actually found a matching `GroupStart`, as opposed
+                // to exhausting the stack because this `GroupEnd` has no open group to close.
+                let mut found_group_start = false;
+
+                while let Some(last) = stack.pop() {
+                    match last {
+                        StackToken::BinaryOp(op) => result.push(PostfixToken::BinaryOp(op)),
+                        StackToken::PrefixOp(op) => result.push(PostfixToken::UnaryOp(op)),
+                        StackToken::Function(_) => {
+                            return Err(ConvertError::UnmatchedFunction);
+                        }
+                        StackToken::GroupStart => {
+                            found_group_start = true;
+                            break;
+                        }
+                    }
+                }
+
+                if !found_group_start {
+                    // Nothing was open to close; `group_is_call` has no matching entry either,
+                    // since it's only ever pushed alongside a real `GroupStart`.
+                    unmatched_group_ends += 1;
+                    continue;
+                }
+                open_groups -= 1;
+
+                // Safe to `unwrap`: pushed alongside every real `GroupStart`, just matched above.
+                if group_is_call.pop().unwrap() {
+                    // Safe to `unwrap`: both pushed alongside `group_is_call` for this call
+                    let start_len = call_start_lens.pop().unwrap();
+                    let counted_args = call_arg_counts.pop().unwrap();
+                    let arg_count = if result.len() == start_len {
+                        0
+                    } else {
+                        counted_args
+                    };
+
+                    match stack.pop() {
+                        Some(StackToken::Function(name)) => {
+                            result.push(PostfixToken::FunctionCall { name, arg_count })
+                        }
+                        _ => unreachable!("Function call group without a Function token"),
+                    }
                 }
             }
-            group_depth -= 1;
         }
-    });
+    }
+
+    // A dangling `Function` at the very end of the stream has no later token to be caught by
+    // the check above.
+    if matches!(stack.last(), Some(StackToken::Function(_))) {
+        return Err(ConvertError::UnmatchedFunction);
+    }
+
+    if open_groups == 0 && unmatched_group_ends == 0 {
+        result.extend(stack.into_iter().rev().map(Into::into));
+        Ok(result)
+    } else {
+        Err(ConvertError::UnbalancedGroups(
+            open_groups as i32 - unmatched_group_ends as i32,
+        ))
+    }
+}
+
+/// Converts an iterator of [`InfixToken`] to a [`Vec`] of [`PostfixToken`], like [`convert`],
+/// but additionally verifies that the token stream is valid by simulating the depth of the
+/// evaluation stack a caller would use to evaluate the result.
+///
+/// Each [`Operand`](InfixToken::Operand) pushes one value; each [`BinaryOp`](InfixToken::BinaryOp)
+/// requires two values and leaves one; each unary operator requires one value and leaves one;
+/// each function call requires `arg_count` values and leaves one. If an operator is ever short of operands,
+/// or the expression doesn't reduce to exactly one final value, conversion fails with a
+/// [`ConvertError`] describing the problem instead of silently producing an unevaluable postfix
+/// stream.
+///
+/// # Errors
+///
+/// See [`ConvertError`].
+pub fn convert_checked<Operand, BinaryOp, UnaryOp, Name, I>(
+    tokens: I,
+) -> Result<Vec<PostfixToken<Operand, BinaryOp, UnaryOp, Name>>, ConvertError>
+where
+    I: IntoIterator<Item = InfixToken<Operand, BinaryOp, UnaryOp, Name>>,
+    BinaryOp: BinaryOperator,
+    UnaryOp: UnaryOperator,
+{
+    let result = convert(tokens)?;
 
-    match group_depth {
-        0 => {
-            result.extend(stack.into_iter().rev().map(Into::into));
-            Ok(result)
+    let mut depth: usize = 0;
+    for (index, token) in result.iter().enumerate() {
+        match token {
+            PostfixToken::Operand(_) => depth += 1,
+            PostfixToken::BinaryOp(_) => {
+                if depth < 2 {
+                    return Err(ConvertError::MissingOperand(index));
+                }
+                depth -= 1;
+            }
+            PostfixToken::UnaryOp(_) => {
+                if depth < 1 {
+                    return Err(ConvertError::MissingOperand(index));
+                }
+            }
+            PostfixToken::FunctionCall { arg_count, .. } => {
+                if depth < *arg_count {
+                    return Err(ConvertError::MissingOperand(index));
+                }
+                depth = depth - arg_count + 1;
+            }
         }
-        group_depth => Err(ConvertError::UnbalancedGroups(group_depth)),
+    }
+
+    match depth {
+        1 => Ok(result),
+        depth => Err(ConvertError::IncompleteExpression(depth)),
     }
 }
 
-fn stack_to_result<BinaryOp>(last: &StackToken<BinaryOp>, op: &BinaryOp) -> bool
+fn stack_to_result<BinaryOp, UnaryOp, Name>(
+    last: &StackToken<BinaryOp, UnaryOp, Name>,
+    precedence: u8,
+    associativity: Associativity,
+) -> bool
 where
     BinaryOp: BinaryOperator,
+    UnaryOp: UnaryOperator,
 {
-    match last {
-        StackToken::BinaryOp(last_op) => last_op.precedence() >= op.precedence(),
-        StackToken::GroupStart => false,
+    let last_precedence = match last {
+        StackToken::BinaryOp(last_op) => last_op.precedence(),
+        StackToken::PrefixOp(last_op) => last_op.precedence(),
+        StackToken::Function(_) | StackToken::GroupStart => return false,
+    };
+
+    match associativity {
+        Associativity::Left | Associativity::None => last_precedence >= precedence,
+        Associativity::Right => last_precedence > precedence,
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{convert, BinaryOperator, ConvertError, InfixToken, PostfixToken};
+    use super::{
+        convert, convert_checked, Associativity, BinaryOperator, ConvertError, InfixToken,
+        PostfixToken, UnaryOperator,
+    };
 
     #[derive(Debug, PartialEq, Eq)]
     enum TestBinaryOp {
@@ -114,9 +351,340 @@ mod tests {
         }
     }
 
+    #[derive(Debug, PartialEq, Eq)]
+    enum TestFixityOp {
+        Pow,
+        Assign,
+        Lt,
+        Mul,
+    }
+
+    impl BinaryOperator for TestFixityOp {
+        fn precedence(&self) -> u8 {
+            match self {
+                TestFixityOp::Pow => 2,
+                TestFixityOp::Assign => 1,
+                TestFixityOp::Lt => 1,
+                TestFixityOp::Mul => 2,
+            }
+        }
+
+        fn associativity(&self) -> Associativity {
+            match self {
+                TestFixityOp::Pow | TestFixityOp::Assign => Associativity::Right,
+                TestFixityOp::Lt => Associativity::None,
+                TestFixityOp::Mul => Associativity::Left,
+            }
+        }
+    }
+
+    #[test]
+    fn test_right_associative() {
+        // `2 ^ 3 ^ 2` should convert as `2 ^ (3 ^ 2)`, i.e. `2 3 2 ^ ^`.
+        let infix_tokens: Vec<InfixToken<&str, TestFixityOp>> = vec![
+            InfixToken::Operand("2"),
+            InfixToken::BinaryOp(TestFixityOp::Pow),
+            InfixToken::Operand("3"),
+            InfixToken::BinaryOp(TestFixityOp::Pow),
+            InfixToken::Operand("2"),
+        ];
+
+        assert_eq!(
+            convert(infix_tokens).unwrap(),
+            vec![
+                PostfixToken::Operand("2"),
+                PostfixToken::Operand("3"),
+                PostfixToken::Operand("2"),
+                PostfixToken::BinaryOp(TestFixityOp::Pow),
+                PostfixToken::BinaryOp(TestFixityOp::Pow),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_right_associative_assignment() {
+        // `a = b = c` should convert as `a = (b = c)`, i.e. `a b c = =`.
+        let infix_tokens: Vec<InfixToken<&str, TestFixityOp>> = vec![
+            InfixToken::Operand("a"),
+            InfixToken::BinaryOp(TestFixityOp::Assign),
+            InfixToken::Operand("b"),
+            InfixToken::BinaryOp(TestFixityOp::Assign),
+            InfixToken::Operand("c"),
+        ];
+
+        assert_eq!(
+            convert(infix_tokens).unwrap(),
+            vec![
+                PostfixToken::Operand("a"),
+                PostfixToken::Operand("b"),
+                PostfixToken::Operand("c"),
+                PostfixToken::BinaryOp(TestFixityOp::Assign),
+                PostfixToken::BinaryOp(TestFixityOp::Assign),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_non_associative_chain_rejected() {
+        // `a < b < c` chains two equal-precedence, non-associative operators.
+        let infix_tokens: Vec<InfixToken<&str, TestFixityOp>> = vec![
+            InfixToken::Operand("a"),
+            InfixToken::BinaryOp(TestFixityOp::Lt),
+            InfixToken::Operand("b"),
+            InfixToken::BinaryOp(TestFixityOp::Lt),
+            InfixToken::Operand("c"),
+        ];
+
+        assert_eq!(
+            convert(infix_tokens).unwrap_err(),
+            ConvertError::NonAssociativeChain
+        );
+    }
+
+    #[test]
+    fn test_non_associative_chain_rejected_across_higher_precedence_op() {
+        // `a < b * c < d` still chains two equal-precedence, non-associative `Lt`s together,
+        // even though a higher-precedence `Mul` sits between them in the input.
+        let infix_tokens: Vec<InfixToken<&str, TestFixityOp>> = vec![
+            InfixToken::Operand("a"),
+            InfixToken::BinaryOp(TestFixityOp::Lt),
+            InfixToken::Operand("b"),
+            InfixToken::BinaryOp(TestFixityOp::Mul),
+            InfixToken::Operand("c"),
+            InfixToken::BinaryOp(TestFixityOp::Lt),
+            InfixToken::Operand("d"),
+        ];
+
+        assert_eq!(
+            convert(infix_tokens).unwrap_err(),
+            ConvertError::NonAssociativeChain
+        );
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum TestUnaryOp {
+        Neg,
+        Factorial,
+    }
+
+    impl UnaryOperator for TestUnaryOp {
+        fn precedence(&self) -> u8 {
+            3
+        }
+    }
+
+    #[test]
+    fn test_prefix_op() {
+        // `-a + b` should convert as `(-a) + b`, i.e. `a NEG b +`.
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp, TestUnaryOp>> = vec![
+            InfixToken::PrefixOp(TestUnaryOp::Neg),
+            InfixToken::Operand("a"),
+            InfixToken::BinaryOp(TestBinaryOp::Add),
+            InfixToken::Operand("b"),
+        ];
+
+        assert_eq!(
+            convert(infix_tokens).unwrap(),
+            vec![
+                PostfixToken::Operand("a"),
+                PostfixToken::UnaryOp(TestUnaryOp::Neg),
+                PostfixToken::Operand("b"),
+                PostfixToken::BinaryOp(TestBinaryOp::Add),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_double_prefix_op() {
+        // `- -a` should convert as `-(-a)`, i.e. `a NEG NEG`.
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp, TestUnaryOp>> = vec![
+            InfixToken::PrefixOp(TestUnaryOp::Neg),
+            InfixToken::PrefixOp(TestUnaryOp::Neg),
+            InfixToken::Operand("a"),
+        ];
+
+        assert_eq!(
+            convert(infix_tokens).unwrap(),
+            vec![
+                PostfixToken::Operand("a"),
+                PostfixToken::UnaryOp(TestUnaryOp::Neg),
+                PostfixToken::UnaryOp(TestUnaryOp::Neg),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_postfix_op() {
+        // `a! + b` should convert as `(a!) + b`, i.e. `a FACT b +`.
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp, TestUnaryOp>> = vec![
+            InfixToken::Operand("a"),
+            InfixToken::PostfixOp(TestUnaryOp::Factorial),
+            InfixToken::BinaryOp(TestBinaryOp::Add),
+            InfixToken::Operand("b"),
+        ];
+
+        assert_eq!(
+            convert(infix_tokens).unwrap(),
+            vec![
+                PostfixToken::Operand("a"),
+                PostfixToken::UnaryOp(TestUnaryOp::Factorial),
+                PostfixToken::Operand("b"),
+                PostfixToken::BinaryOp(TestBinaryOp::Add),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prefix_op_in_group() {
+        // `(-a + b) * c` should convert as `a NEG b + c *`.
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp, TestUnaryOp>> = vec![
+            InfixToken::GroupStart,
+            InfixToken::PrefixOp(TestUnaryOp::Neg),
+            InfixToken::Operand("a"),
+            InfixToken::BinaryOp(TestBinaryOp::Add),
+            InfixToken::Operand("b"),
+            InfixToken::GroupEnd,
+            InfixToken::BinaryOp(TestBinaryOp::Mul),
+            InfixToken::Operand("c"),
+        ];
+
+        assert_eq!(
+            convert(infix_tokens).unwrap(),
+            vec![
+                PostfixToken::Operand("a"),
+                PostfixToken::UnaryOp(TestUnaryOp::Neg),
+                PostfixToken::Operand("b"),
+                PostfixToken::BinaryOp(TestBinaryOp::Add),
+                PostfixToken::Operand("c"),
+                PostfixToken::BinaryOp(TestBinaryOp::Mul),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_function_call() {
+        // `max(a, b + 1, c)` should convert as `a b 1 + c max(3 args)`.
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp, std::convert::Infallible, &str>> = vec![
+            InfixToken::Function("max"),
+            InfixToken::GroupStart,
+            InfixToken::Operand("a"),
+            InfixToken::ArgSeparator,
+            InfixToken::Operand("b"),
+            InfixToken::BinaryOp(TestBinaryOp::Add),
+            InfixToken::Operand("1"),
+            InfixToken::ArgSeparator,
+            InfixToken::Operand("c"),
+            InfixToken::GroupEnd,
+        ];
+
+        assert_eq!(
+            convert(infix_tokens).unwrap(),
+            vec![
+                PostfixToken::Operand("a"),
+                PostfixToken::Operand("b"),
+                PostfixToken::Operand("1"),
+                PostfixToken::BinaryOp(TestBinaryOp::Add),
+                PostfixToken::Operand("c"),
+                PostfixToken::FunctionCall {
+                    name: "max",
+                    arg_count: 3
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_function_call_no_args() {
+        // `now()` should convert as `now(0 args)`.
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp, std::convert::Infallible, &str>> = vec![
+            InfixToken::Function("now"),
+            InfixToken::GroupStart,
+            InfixToken::GroupEnd,
+        ];
+
+        assert_eq!(
+            convert(infix_tokens).unwrap(),
+            vec![PostfixToken::FunctionCall {
+                name: "now",
+                arg_count: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_nested_function_calls() {
+        // `f(g(x), y)` should convert as `x g(1 args) y f(2 args)`.
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp, std::convert::Infallible, &str>> = vec![
+            InfixToken::Function("f"),
+            InfixToken::GroupStart,
+            InfixToken::Function("g"),
+            InfixToken::GroupStart,
+            InfixToken::Operand("x"),
+            InfixToken::GroupEnd,
+            InfixToken::ArgSeparator,
+            InfixToken::Operand("y"),
+            InfixToken::GroupEnd,
+        ];
+
+        assert_eq!(
+            convert(infix_tokens).unwrap(),
+            vec![
+                PostfixToken::Operand("x"),
+                PostfixToken::FunctionCall {
+                    name: "g",
+                    arg_count: 1
+                },
+                PostfixToken::Operand("y"),
+                PostfixToken::FunctionCall {
+                    name: "f",
+                    arg_count: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unmatched_function_not_followed_by_group_start() {
+        // `f)` - a function name with no argument list at all, immediately unwound by an
+        // unrelated `GroupEnd`.
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp, std::convert::Infallible, &str>> =
+            vec![InfixToken::Function("f"), InfixToken::GroupEnd];
+
+        assert_eq!(
+            convert(infix_tokens).unwrap_err(),
+            ConvertError::UnmatchedFunction
+        );
+    }
+
+    #[test]
+    fn test_unmatched_function_at_end_of_stream() {
+        // `f` - a function name with nothing at all following it.
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp, std::convert::Infallible, &str>> =
+            vec![InfixToken::Function("f")];
+
+        assert_eq!(
+            convert(infix_tokens).unwrap_err(),
+            ConvertError::UnmatchedFunction
+        );
+    }
+
+    #[test]
+    fn test_stray_arg_separator() {
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp>> = vec![
+            InfixToken::Operand("a"),
+            InfixToken::ArgSeparator,
+            InfixToken::Operand("b"),
+        ];
+
+        assert_eq!(
+            convert(infix_tokens).unwrap_err(),
+            ConvertError::StrayArgSeparator
+        );
+    }
+
     #[test]
     fn test_ok_1() {
-        let infix_tokens = vec![
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp>> = vec![
             InfixToken::Operand("m"),
             InfixToken::BinaryOp(TestBinaryOp::Mul),
             InfixToken::Operand("n"),
@@ -148,7 +716,7 @@ mod tests {
 
     #[test]
     fn test_ok_2() {
-        let infix_tokens = vec![
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp>> = vec![
             InfixToken::Operand("a"),
             InfixToken::BinaryOp(TestBinaryOp::Add),
             InfixToken::Operand("b"),
@@ -174,7 +742,7 @@ mod tests {
 
     #[test]
     fn test_ok_3() {
-        let infix_tokens = vec![
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp>> = vec![
             InfixToken::GroupStart,
             InfixToken::GroupStart,
             InfixToken::Operand("a"),
@@ -214,7 +782,7 @@ mod tests {
 
     #[test]
     fn test_bad_1() {
-        let infix_tokens = vec![
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp>> = vec![
             InfixToken::GroupStart,
             InfixToken::GroupStart,
             InfixToken::Operand("a"),
@@ -243,7 +811,7 @@ mod tests {
 
     #[test]
     fn test_bad_2() {
-        let infix_tokens = vec![
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp>> = vec![
             InfixToken::GroupStart,
             InfixToken::GroupStart,
             InfixToken::Operand("a"),
@@ -268,4 +836,106 @@ mod tests {
         assert_eq!(result, ConvertError::UnbalancedGroups(1));
         assert_eq!(result.to_string(), "Unbalanced groups");
     }
+
+    #[test]
+    fn test_bad_3() {
+        // An extra `GroupEnd` with no matching `GroupStart`.
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp>> = vec![
+            InfixToken::Operand("a"),
+            InfixToken::BinaryOp(TestBinaryOp::Add),
+            InfixToken::Operand("b"),
+            InfixToken::GroupEnd, // Stray
+        ];
+
+        let result = convert(infix_tokens).unwrap_err();
+
+        assert_eq!(result, ConvertError::UnbalancedGroups(-1));
+        assert_eq!(result.to_string(), "Unbalanced groups");
+    }
+
+    #[test]
+    fn test_bad_4() {
+        // A stray `GroupEnd` with nothing open, followed later by an unrelated `GroupStart`
+        // that never closes. The two imbalances must not cancel each other out.
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp>> = vec![
+            InfixToken::Operand("a"),
+            InfixToken::GroupEnd, // Stray
+            InfixToken::BinaryOp(TestBinaryOp::Add),
+            InfixToken::GroupStart, // Never closed
+            InfixToken::Operand("b"),
+        ];
+
+        assert!(matches!(
+            convert(infix_tokens).unwrap_err(),
+            ConvertError::UnbalancedGroups(_)
+        ));
+    }
+
+    #[test]
+    fn test_checked_ok() {
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp>> = vec![
+            InfixToken::Operand("a"),
+            InfixToken::BinaryOp(TestBinaryOp::Add),
+            InfixToken::Operand("b"),
+        ];
+
+        assert_eq!(
+            convert_checked(infix_tokens).unwrap(),
+            vec![
+                PostfixToken::Operand("a"),
+                PostfixToken::Operand("b"),
+                PostfixToken::BinaryOp(TestBinaryOp::Add),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_checked_missing_operand() {
+        // `a +` is missing its second operand.
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp>> = vec![
+            InfixToken::Operand("a"),
+            InfixToken::BinaryOp(TestBinaryOp::Add),
+        ];
+
+        assert_eq!(
+            convert_checked(infix_tokens).unwrap_err(),
+            ConvertError::MissingOperand(1)
+        );
+    }
+
+    #[test]
+    fn test_checked_incomplete_expression() {
+        // `a b` has two operands but no operator to combine them.
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp>> =
+            vec![InfixToken::Operand("a"), InfixToken::Operand("b")];
+
+        assert_eq!(
+            convert_checked(infix_tokens).unwrap_err(),
+            ConvertError::IncompleteExpression(2)
+        );
+    }
+
+    #[test]
+    fn test_checked_function_call() {
+        let infix_tokens: Vec<InfixToken<&str, TestBinaryOp, std::convert::Infallible, &str>> = vec![
+            InfixToken::Function("max"),
+            InfixToken::GroupStart,
+            InfixToken::Operand("a"),
+            InfixToken::ArgSeparator,
+            InfixToken::Operand("b"),
+            InfixToken::GroupEnd,
+        ];
+
+        assert_eq!(
+            convert_checked(infix_tokens).unwrap(),
+            vec![
+                PostfixToken::Operand("a"),
+                PostfixToken::Operand("b"),
+                PostfixToken::FunctionCall {
+                    name: "max",
+                    arg_count: 2
+                },
+            ]
+        );
+    }
 }
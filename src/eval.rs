@@ -0,0 +1,245 @@
+use super::tokens::PostfixToken;
+use std::{error::Error, fmt};
+
+/// An error while evaluating a [`PostfixToken`] stream with [`evaluate`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum EvalError<E> {
+    /// An operator was reached with fewer than the values it needed already on the stack,
+    /// or the stream produced no final value at all.
+    StackUnderflow,
+    /// More than one value remained on the stack once every token had been processed. Holds
+    /// the number of extra values beyond the single expected result.
+    ExtraOperands(usize),
+    /// The caller-supplied `apply` closure returned an error, for example a division by zero.
+    Apply(E),
+}
+
+impl<E: fmt::Display> fmt::Display for EvalError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::StackUnderflow => write!(f, "Not enough operands on the evaluation stack"),
+            EvalError::ExtraOperands(n) => {
+                write!(f, "{n} extra operand(s) left on the evaluation stack")
+            }
+            EvalError::Apply(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for EvalError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EvalError::Apply(err) => Some(err),
+            EvalError::StackUnderflow | EvalError::ExtraOperands(_) => None,
+        }
+    }
+}
+
+/// Evaluates a [`PostfixToken`] stream (as produced by [`convert`](super::convert) or
+/// [`convert_checked`](super::convert_checked)) by walking it with a value stack.
+///
+/// Each [`Operand`](PostfixToken::Operand) is converted to a `V` via [`From`] and pushed onto
+/// the stack. Each [`BinaryOp`](PostfixToken::BinaryOp) pops the top two values (`lhs` below
+/// `rhs`) and calls `apply_binary(op, lhs, rhs)`, pushing the result back on. Each
+/// [`UnaryOp`](PostfixToken::UnaryOp) pops the top value and calls `apply_unary(op, value)`.
+/// Each [`FunctionCall`](PostfixToken::FunctionCall) pops its `arg_count` values off (in the
+/// order they were pushed) and calls `apply_function(name, args)`. In every case the closure's
+/// result is pushed back onto the stack. Once the stream is exhausted, the single remaining
+/// value is returned.
+///
+/// This lets `fixit` drive a calculator end-to-end rather than only reordering tokens; callers
+/// are still responsible for defining what each `BinaryOp`, `UnaryOp` and named function
+/// actually does. A stream with no unary operators or function calls can leave `UnaryOp` and
+/// `Name` defaulted to [`Infallible`](std::convert::Infallible) and pass `|op, _| match op {}`
+/// and `|name, _| match name {}` for the corresponding closures.
+///
+/// # Errors
+///
+/// See [`EvalError`].
+pub fn evaluate<Operand, BinaryOp, UnaryOp, Name, V, E, I>(
+    tokens: I,
+    mut apply_binary: impl FnMut(BinaryOp, V, V) -> Result<V, E>,
+    mut apply_unary: impl FnMut(UnaryOp, V) -> Result<V, E>,
+    mut apply_function: impl FnMut(Name, Vec<V>) -> Result<V, E>,
+) -> Result<V, EvalError<E>>
+where
+    I: IntoIterator<Item = PostfixToken<Operand, BinaryOp, UnaryOp, Name>>,
+    V: From<Operand>,
+{
+    let mut stack: Vec<V> = vec![];
+
+    for token in tokens {
+        match token {
+            PostfixToken::Operand(operand) => stack.push(V::from(operand)),
+            PostfixToken::BinaryOp(op) => {
+                let rhs = stack.pop().ok_or(EvalError::StackUnderflow)?;
+                let lhs = stack.pop().ok_or(EvalError::StackUnderflow)?;
+                stack.push(apply_binary(op, lhs, rhs).map_err(EvalError::Apply)?);
+            }
+            PostfixToken::UnaryOp(op) => {
+                let value = stack.pop().ok_or(EvalError::StackUnderflow)?;
+                stack.push(apply_unary(op, value).map_err(EvalError::Apply)?);
+            }
+            PostfixToken::FunctionCall { name, arg_count } => {
+                if stack.len() < arg_count {
+                    return Err(EvalError::StackUnderflow);
+                }
+                let args = stack.split_off(stack.len() - arg_count);
+                stack.push(apply_function(name, args).map_err(EvalError::Apply)?);
+            }
+        }
+    }
+
+    match stack.len() {
+        1 => Ok(stack.pop().unwrap()),
+        0 => Err(EvalError::StackUnderflow),
+        n => Err(EvalError::ExtraOperands(n - 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{evaluate, EvalError};
+    use crate::{convert, BinaryOperator, InfixToken, PostfixToken, UnaryOperator};
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum TestBinaryOp {
+        Add,
+        Mul,
+        Div,
+    }
+
+    impl BinaryOperator for TestBinaryOp {
+        fn precedence(&self) -> u8 {
+            match self {
+                TestBinaryOp::Add => 1,
+                TestBinaryOp::Mul => 2,
+                TestBinaryOp::Div => 2,
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct DivByZero;
+
+    fn apply(op: TestBinaryOp, lhs: i64, rhs: i64) -> Result<i64, DivByZero> {
+        match op {
+            TestBinaryOp::Add => Ok(lhs + rhs),
+            TestBinaryOp::Mul => Ok(lhs * rhs),
+            TestBinaryOp::Div if rhs == 0 => Err(DivByZero),
+            TestBinaryOp::Div => Ok(lhs / rhs),
+        }
+    }
+
+    // No unary operators or function calls are used by these tests, so `UnaryOp`/`Name` stay
+    // defaulted to `Infallible` and these closures can never actually run.
+    fn apply_unary(op: std::convert::Infallible, _value: i64) -> Result<i64, DivByZero> {
+        match op {}
+    }
+
+    fn apply_function(name: std::convert::Infallible, _args: Vec<i64>) -> Result<i64, DivByZero> {
+        match name {}
+    }
+
+    #[test]
+    fn test_evaluate_ok() {
+        // `2 + 3 * 4` should evaluate to `14`.
+        let infix_tokens = vec![
+            InfixToken::Operand(2),
+            InfixToken::BinaryOp(TestBinaryOp::Add),
+            InfixToken::Operand(3),
+            InfixToken::BinaryOp(TestBinaryOp::Mul),
+            InfixToken::Operand(4),
+        ];
+
+        let postfix_tokens = convert(infix_tokens).unwrap();
+
+        assert_eq!(
+            evaluate(postfix_tokens, apply, apply_unary, apply_function).unwrap(),
+            14
+        );
+    }
+
+    #[test]
+    fn test_evaluate_apply_error() {
+        let infix_tokens = vec![
+            InfixToken::Operand(1),
+            InfixToken::BinaryOp(TestBinaryOp::Div),
+            InfixToken::Operand(0),
+        ];
+
+        let postfix_tokens = convert(infix_tokens).unwrap();
+
+        assert_eq!(
+            evaluate(postfix_tokens, apply, apply_unary, apply_function).unwrap_err(),
+            EvalError::Apply(DivByZero)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_stack_underflow() {
+        let postfix_tokens: Vec<PostfixToken<i64, TestBinaryOp>> =
+            vec![PostfixToken::BinaryOp(TestBinaryOp::Add)];
+
+        assert_eq!(
+            evaluate(postfix_tokens, apply, apply_unary, apply_function).unwrap_err(),
+            EvalError::StackUnderflow
+        );
+    }
+
+    #[test]
+    fn test_evaluate_extra_operands() {
+        let infix_tokens = vec![InfixToken::Operand(1), InfixToken::Operand(2)];
+
+        let postfix_tokens = convert(infix_tokens).unwrap();
+
+        assert_eq!(
+            evaluate(postfix_tokens, apply, apply_unary, apply_function).unwrap_err(),
+            EvalError::ExtraOperands(1)
+        );
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum TestUnaryOp {
+        Neg,
+    }
+
+    impl UnaryOperator for TestUnaryOp {
+        fn precedence(&self) -> u8 {
+            3
+        }
+    }
+
+    fn apply_neg(op: TestUnaryOp, value: i64) -> Result<i64, DivByZero> {
+        match op {
+            TestUnaryOp::Neg => Ok(-value),
+        }
+    }
+
+    fn apply_max(_name: &str, args: Vec<i64>) -> Result<i64, DivByZero> {
+        // Safe to `unwrap` because `convert` never emits a `FunctionCall` with zero arguments
+        // unless the source expression called it with none, which `max()` here does not.
+        Ok(args.into_iter().max().unwrap())
+    }
+
+    #[test]
+    fn test_evaluate_unary_and_function_call() {
+        // `max(-a, b)` with `a = 3`, `b = 5` should evaluate to `5`.
+        let infix_tokens: Vec<InfixToken<i64, TestBinaryOp, TestUnaryOp, &str>> = vec![
+            InfixToken::Function("max"),
+            InfixToken::GroupStart,
+            InfixToken::PrefixOp(TestUnaryOp::Neg),
+            InfixToken::Operand(3),
+            InfixToken::ArgSeparator,
+            InfixToken::Operand(5),
+            InfixToken::GroupEnd,
+        ];
+
+        let postfix_tokens = convert(infix_tokens).unwrap();
+
+        assert_eq!(
+            evaluate(postfix_tokens, apply, apply_neg, apply_max).unwrap(),
+            5
+        );
+    }
+}
@@ -57,7 +57,9 @@
 //! ```
 
 mod algorithm;
+mod eval;
 mod tokens;
 
-pub use algorithm::{convert, ConvertError};
-pub use tokens::{BinaryOperator, InfixToken, PostfixToken};
+pub use algorithm::{convert, convert_checked, ConvertError};
+pub use eval::{evaluate, EvalError};
+pub use tokens::{Associativity, BinaryOperator, InfixToken, PostfixToken, UnaryOperator};
@@ -1,12 +1,27 @@
 /// A input token, presumably from a parsed stream defining an arithmetic-like expression in human-readable ("infix") order.
 ///
-/// Note that there is no `UnaryOp` arm; it is assumed that unary operators have already been encoded as a single [`Operand`](InfixToken::Operand).
+/// The `UnaryOp` and `Name` type parameters default to [`Infallible`](std::convert::Infallible)
+/// so that streams with no unary operators or function calls don't need to name them.
 #[derive(Debug, PartialEq, Eq)]
-pub enum InfixToken<Operand, BinaryOp> {
+pub enum InfixToken<
+    Operand,
+    BinaryOp,
+    UnaryOp = std::convert::Infallible,
+    Name = std::convert::Infallible,
+> {
     /// An operand that may be operated on. This might be a scalar, a list, a function call, etc.
     Operand(Operand),
     /// A binary operator, for example an addition of two values.
     BinaryOp(BinaryOp),
+    /// A unary operator appearing before its operand, for example a unary minus in `-a`.
+    PrefixOp(UnaryOp),
+    /// A unary operator appearing after its operand, for example a factorial in `a!`.
+    PostfixOp(UnaryOp),
+    /// The name of a function being called, immediately followed by a [`GroupStart`](InfixToken::GroupStart)
+    /// opening its argument list, e.g. `max` in `max(a, b)`.
+    Function(Name),
+    /// A separator between two arguments of a function call, e.g. the comma in `max(a, b)`.
+    ArgSeparator,
     /// An abstract start of a group.
     /// Corresponds to a left parenthesis (`(`) in a typical arithmetic expression.
     GroupStart,
@@ -17,15 +32,34 @@ pub enum InfixToken<Operand, BinaryOp> {
 
 /// An output token.
 ///
-/// This is the subset of an [`InfixToken`] excluding the [`GroupStart`](InfixToken::GroupStart`) and [`GroupEnd`](InfixToken::GroupEnd) arms.
+/// This is the subset of an [`InfixToken`] excluding the [`GroupStart`](InfixToken::GroupStart`),
+/// [`GroupEnd`](InfixToken::GroupEnd`) and [`ArgSeparator`](InfixToken::ArgSeparator) arms, with
+/// [`PrefixOp`](InfixToken::PrefixOp) and [`PostfixOp`](InfixToken::PostfixOp) collapsed into a
+/// single [`UnaryOp`](PostfixToken::UnaryOp) arm, and [`Function`](InfixToken::Function) paired
+/// with its matching [`GroupStart`](InfixToken::GroupStart)/[`GroupEnd`](InfixToken::GroupEnd) into [`FunctionCall`](PostfixToken::FunctionCall).
 ///
 /// Those arms are unnecessary in a postfix expression because operators are immediately applied to the top values on the evaluation stack.
 #[derive(Debug, PartialEq, Eq)]
-pub enum PostfixToken<Operand, BinaryOp> {
+pub enum PostfixToken<
+    Operand,
+    BinaryOp,
+    UnaryOp = std::convert::Infallible,
+    Name = std::convert::Infallible,
+> {
     /// An operand that may be operated on. This might be a scalar, a list, a function call, etc.
     Operand(Operand),
     /// A binary operator, for example an addition of two values.
     BinaryOp(BinaryOp),
+    /// A unary operator, applied to the value already at the top of the evaluation stack.
+    UnaryOp(UnaryOp),
+    /// A function call, applied to the `arg_count` values already at the top of the evaluation stack.
+    FunctionCall {
+        /// The name of the function being called.
+        name: Name,
+        /// The number of arguments the call was made with. Zero for a call with an empty
+        /// argument list, e.g. `now()`.
+        arg_count: usize,
+    },
 }
 
 /// Trait required for values used as the `BinaryOp` arm of an [`InfixToken`] or [`PostfixToken`].
@@ -61,17 +95,90 @@ pub trait BinaryOperator {
     ///
     /// Actual precedence values do not matter, only their relative values.
     fn precedence(&self) -> u8;
+
+    /// The associativity (fixity) of the operator, used to decide the grouping of two
+    /// operators of equal [`precedence`](Self::precedence) that appear next to each other.
+    ///
+    /// Defaults to [`Associativity::Left`], matching the behaviour of every release before
+    /// this method was added.
+    fn associativity(&self) -> Associativity {
+        Associativity::Left
+    }
+}
+
+/// The associativity (fixity) of a [`BinaryOperator`], i.e. how two operators of equal
+/// [`precedence`](BinaryOperator::precedence) standing next to each other should be grouped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    /// The operator groups with operands to its left, e.g. `a - b - c` is `(a - b) - c`.
+    Left,
+    /// The operator groups with operands to its right, e.g. `a ^ b ^ c` is `a ^ (b ^ c)`.
+    Right,
+    /// The operator does not associate with another operator of the same precedence at all;
+    /// chaining two such operators without disambiguating parentheses is an error.
+    None,
+}
+
+/// Trait required for values used as the `UnaryOp` arm of an [`InfixToken`] or [`PostfixToken`].
+///
+/// # Example
+///
+/// ```
+/// enum MyUnaryOp {
+///     Neg,
+///     Factorial,
+/// }
+///
+/// impl fixit::UnaryOperator for MyUnaryOp {
+///     fn precedence(&self) -> u8 {
+///         match self {
+///             MyUnaryOp::Neg => 3,
+///             MyUnaryOp::Factorial => 3,
+///         }
+///     }
+/// }
+/// ```
+pub trait UnaryOperator {
+    /// The precedence of the operator, i.e. how tightly it is bound to its operand.
+    /// Higher values mean operators that are applied first.
+    ///
+    /// Unary operators are conventionally given a higher precedence than any [`BinaryOperator`]
+    /// they may appear alongside, so that e.g. `-a + b` parses as `(-a) + b`.
+    ///
+    /// Only consulted for a [`PrefixOp`](InfixToken::PrefixOp). A
+    /// [`PostfixOp`](InfixToken::PostfixOp) is emitted as soon as it is seen, applying to the
+    /// operand, group or call just before it whatever its precedence.
+    fn precedence(&self) -> u8;
+}
+
+// `Infallible` is the default `UnaryOp` for token streams with no unary operators; it can never
+// be constructed, so this impl is never actually called, but it lets `convert` stay generic
+// without forcing every caller to name a `UnaryOp` type.
+impl UnaryOperator for std::convert::Infallible {
+    fn precedence(&self) -> u8 {
+        match *self {}
+    }
 }
 
-pub(crate) enum StackToken<BinaryOp> {
+pub(crate) enum StackToken<BinaryOp, UnaryOp, Name> {
     BinaryOp(BinaryOp),
+    PrefixOp(UnaryOp),
+    Function(Name),
     GroupStart,
 }
 
-impl<Operand, BinaryOp> From<StackToken<BinaryOp>> for PostfixToken<Operand, BinaryOp> {
-    fn from(value: StackToken<BinaryOp>) -> Self {
+impl<Operand, BinaryOp, UnaryOp, Name> From<StackToken<BinaryOp, UnaryOp, Name>>
+    for PostfixToken<Operand, BinaryOp, UnaryOp, Name>
+{
+    fn from(value: StackToken<BinaryOp, UnaryOp, Name>) -> Self {
         match value {
             StackToken::BinaryOp(op) => PostfixToken::BinaryOp(op),
+            StackToken::PrefixOp(op) => PostfixToken::UnaryOp(op),
+            // `convert` rejects a `Function` not immediately followed by `GroupStart` with
+            // `ConvertError::UnmatchedFunction` before any `StackToken` reaches here, so every
+            // `Function` left on the stack is already paired with a matching `GroupStart`/
+            // `GroupEnd` and consumed directly by `convert` instead of via this conversion.
+            StackToken::Function(_) => unreachable!("Function token without matching GroupStart"),
             StackToken::GroupStart => unreachable!("Unbalanced groups"),
         }
     }